@@ -0,0 +1,287 @@
+//! BEP 9 magnet links: a trackerless entry point carrying just enough to
+//! start a metadata fetch (info-hash, display name, trackers) instead of a
+//! full `.torrent` file.
+
+use crate::Torrent;
+
+/// A parsed `magnet:?...` URI, or the minimal data needed to build one.
+#[derive(Debug, PartialEq)]
+pub struct MagnetLink {
+    pub info_hash: [u8; 20],
+    pub display_name: Option<String>,
+    pub trackers: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MagnetError {
+    /// The URI did not start with `magnet:?`.
+    Scheme(String),
+    /// The `xt` parameter was missing, malformed, or not a `btih` info-hash.
+    InfoHash(String),
+    /// A percent-encoded parameter value was malformed.
+    Encoding(String),
+}
+
+/// Builds a magnet URI from a parsed torrent: `xt` carries the v1
+/// info-hash as hex, `dn` the torrent name, and `tr` each tracker from
+/// `announce` and `announce-list`, in order and deduplicated.
+pub fn to_magnet_uri(torrent: &Torrent) -> String {
+    let mut uri = format!("magnet:?xt=urn:btih:{}", torrent.info_hash_hex());
+    uri.push_str(&format!(
+        "&dn={}",
+        crate::tracker::percent_encode(torrent.info.name.as_bytes())
+    ));
+    for url in trackers(torrent) {
+        uri.push_str(&format!("&tr={}", crate::tracker::percent_encode(url.as_bytes())));
+    }
+    uri
+}
+
+/// `announce` followed by every `announce-list` entry, in tier order, with
+/// duplicates of an already-listed URL dropped.
+fn trackers(torrent: &Torrent) -> Vec<&str> {
+    let mut seen = std::collections::HashSet::new();
+    let mut out = Vec::new();
+
+    let all = std::iter::once(&torrent.announce).chain(
+        torrent
+            .announce_list
+            .iter()
+            .flatten()
+            .flatten(),
+    );
+    for url in all {
+        if seen.insert(url.as_str()) {
+            out.push(url.as_str());
+        }
+    }
+
+    out
+}
+
+/// Parses a `magnet:?...` URI into a [`MagnetLink`]. Accepts both the
+/// 40-char hex and 32-char base32 forms of `xt=urn:btih:`.
+pub fn parse_magnet(uri: &str) -> Result<MagnetLink, MagnetError> {
+    let query = uri
+        .strip_prefix("magnet:?")
+        .ok_or_else(|| MagnetError::Scheme(format!("not a magnet uri: {uri}")))?;
+
+    let mut info_hash = None;
+    let mut display_name = None;
+    let mut trackers = Vec::new();
+
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        match key {
+            "xt" => {
+                let btih = value
+                    .strip_prefix("urn:btih:")
+                    .ok_or_else(|| MagnetError::InfoHash(format!("unsupported xt value: {value}")))?;
+                info_hash = Some(decode_info_hash(btih)?);
+            }
+            "dn" => display_name = Some(percent_decode(value)?),
+            "tr" => trackers.push(percent_decode(value)?),
+            _ => {}
+        }
+    }
+
+    let info_hash =
+        info_hash.ok_or_else(|| MagnetError::InfoHash("missing xt=urn:btih: parameter".to_string()))?;
+
+    Ok(MagnetLink {
+        info_hash,
+        display_name,
+        trackers,
+    })
+}
+
+fn decode_info_hash(btih: &str) -> Result<[u8; 20], MagnetError> {
+    let bytes = match btih.len() {
+        40 => hex_decode(btih).map_err(MagnetError::InfoHash)?,
+        32 => base32_decode(btih).map_err(MagnetError::InfoHash)?,
+        other => {
+            return Err(MagnetError::InfoHash(format!(
+                "btih must be 40 hex or 32 base32 characters, got {other}"
+            )));
+        }
+    };
+
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        MagnetError::InfoHash(format!("btih decoded to {} bytes, expected 20", bytes.len()))
+    })
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
+    if !s.is_ascii() {
+        return Err(format!("hex string contains non-ascii characters: {s}"));
+    }
+    if s.len() % 2 != 0 {
+        return Err(format!("hex string has odd length: {s}"));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|err| format!("invalid hex digit in {s}: {err}"))
+        })
+        .collect()
+}
+
+/// RFC 4648 base32 (no padding), as used by BEP 9's alternate 32-char
+/// info-hash form: 32 symbols decode to exactly 160 bits with no leftover.
+fn base32_decode(s: &str) -> Result<Vec<u8>, String> {
+    let mut bits: u64 = 0;
+    let mut bit_count = 0;
+    let mut out = Vec::new();
+
+    for c in s.chars() {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u8 - b'A',
+            c @ '2'..='7' => c as u8 - b'2' + 26,
+            _ => return Err(format!("invalid base32 character in {s}: {c}")),
+        };
+        bits = (bits << 5) | value as u64;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+fn percent_decode(s: &str) -> Result<String, MagnetError> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if i + 3 > bytes.len() {
+                return Err(MagnetError::Encoding(format!("truncated percent-encoding in {s}")));
+            }
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .map_err(|_| MagnetError::Encoding(format!("invalid percent-encoding in {s}")))?;
+            let byte = u8::from_str_radix(hex, 16)
+                .map_err(|_| MagnetError::Encoding(format!("invalid percent-encoding in {s}")))?;
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(out)
+        .map_err(|err| MagnetError::Encoding(format!("invalid utf-8 after percent-decoding: {err}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Info, Torrent};
+
+    fn torrent_for(name: &str, announce: &str, announce_list: Option<Vec<Vec<String>>>) -> Torrent {
+        Torrent {
+            announce: announce.to_string(),
+            announce_list,
+            info: Info {
+                name: name.to_string(),
+                piece_length: 16384,
+                pieces: b"01234567890123456789".to_vec(),
+                length: Some(11),
+                files: None,
+                meta_version: None,
+                file_tree: None,
+            },
+            info_hash: [0xab; 20],
+            info_hash_v2: None,
+            piece_layers: None,
+        }
+    }
+
+    #[test]
+    fn builds_magnet_uri_with_name_and_trackers() {
+        let torrent = torrent_for(
+            "blind spot",
+            "http://tracker.example.com/announce",
+            Some(vec![vec!["http://backup.example.com/announce".to_string()]]),
+        );
+
+        let uri = to_magnet_uri(&torrent);
+        assert_eq!(
+            uri,
+            "magnet:?xt=urn:btih:abababababababababababababababababababab\
+&dn=blind%20spot\
+&tr=http%3A%2F%2Ftracker.example.com%2Fannounce\
+&tr=http%3A%2F%2Fbackup.example.com%2Fannounce"
+        );
+    }
+
+    #[test]
+    fn skips_duplicate_trackers() {
+        let torrent = torrent_for(
+            "blindspot",
+            "http://tracker.example.com/announce",
+            Some(vec![vec!["http://tracker.example.com/announce".to_string()]]),
+        );
+
+        let uri = to_magnet_uri(&torrent);
+        assert_eq!(uri.matches("&tr=").count(), 1);
+    }
+
+    #[test]
+    fn parses_hex_btih_dn_and_trackers() {
+        let uri = "magnet:?xt=urn:btih:abababababababababababababababababababab&dn=blind%20spot&tr=http%3A%2F%2Ftracker.example.com%2Fannounce";
+        let link = parse_magnet(uri).unwrap();
+        assert_eq!(link.info_hash, [0xab; 20]);
+        assert_eq!(link.display_name, Some("blind spot".to_string()));
+        assert_eq!(link.trackers, vec!["http://tracker.example.com/announce".to_string()]);
+    }
+
+    #[test]
+    fn parses_base32_btih() {
+        let hex_uri = "magnet:?xt=urn:btih:abababababababababababababababababababab";
+        let base32_uri = "magnet:?xt=urn:btih:VOV2XK5LVOV2XK5LVOV2XK5LVOV2XK5L";
+        assert_eq!(
+            parse_magnet(hex_uri).unwrap().info_hash,
+            parse_magnet(base32_uri).unwrap().info_hash
+        );
+    }
+
+    #[test]
+    fn rejects_missing_scheme() {
+        assert!(parse_magnet("http://example.com").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_xt() {
+        assert!(parse_magnet("magnet:?dn=blindspot").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_btih_length() {
+        assert!(parse_magnet("magnet:?xt=urn:btih:abcd").is_err());
+    }
+
+    #[test]
+    fn rejects_non_ascii_btih_instead_of_panicking() {
+        let uri = format!("magnet:?xt=urn:btih:a{}", "\u{20ac}".repeat(13));
+        assert!(parse_magnet(&uri).is_err());
+    }
+
+    #[test]
+    fn round_trips_through_generation_and_parsing() {
+        let torrent = torrent_for(
+            "blindspot",
+            "http://tracker.example.com/announce",
+            None,
+        );
+        let uri = to_magnet_uri(&torrent);
+        let link = parse_magnet(&uri).unwrap();
+        assert_eq!(link.info_hash, torrent.info_hash);
+        assert_eq!(link.display_name, Some(torrent.info.name.clone()));
+        assert_eq!(link.trackers, vec![torrent.announce.clone()]);
+    }
+}
@@ -1,5 +1,11 @@
 use std::collections::HashMap;
 
+mod magnet;
+mod sha1;
+mod sha256;
+mod tracker;
+mod verify;
+
 /// Bencoding
 #[derive(PartialEq, Debug, Clone)]
 enum BencodeValue<'a> {
@@ -45,10 +51,32 @@ struct BDictionary<'a> {
 /// Metainfo files (also known as .torrent files)
 #[derive(Debug, PartialEq)]
 struct Torrent {
-    /// The URL of the tracker.
+    /// The URL of the tracker. Mandated by the spec, but some torrents only
+    /// carry a usable tracker in `announce_list`; when that happens this is
+    /// filled in from the first tier's first entry instead.
     announce: String,
+    /// BEP 12 multi-tracker extension: tiers of tracker URLs, tried in tier
+    /// order with the URLs within a tier shuffled by the client. `None` when
+    /// the torrent has no `announce-list` key.
+    announce_list: Option<Vec<Vec<String>>>,
     /// The metadata
     info: Info,
+    /// The SHA-1 of the raw bencoded `info` dictionary, exactly as it
+    /// appeared in the file. Required for tracker announces and peer
+    /// handshakes, so it is computed from the original bytes rather than
+    /// from `info.to_bencode_value()`, since re-encoding can disagree with
+    /// a non-canonically-encoded source file.
+    info_hash: [u8; 20],
+
+    /// BEP 52: the SHA-256 of the raw `info` dict, present for v2 and hybrid
+    /// torrents (`meta version` 2). `None` for plain v1 torrents.
+    info_hash_v2: Option<[u8; 32]>,
+
+    /// BEP 52: for each v2 file's `pieces root`, the concatenated SHA-256
+    /// hashes of that file's "piece layer" (one piece-length chunk of
+    /// 16 KiB-block leaves per hash). Top-level key, a sibling of `info`.
+    /// `None` for v1 torrents.
+    piece_layers: Option<HashMap<[u8; 32], Vec<u8>>>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -68,6 +96,7 @@ struct Info {
     /// pieces maps to a string whose length is a multiple of 20.
     /// It is to be subdivided into strings of length 20,
     /// each of which is the SHA1 hash of the piece at the corresponding index.
+    /// Empty for a pure v2 torrent, which carries no flat v1 piece list.
     pieces: Vec<u8>,
 
     /// In the single file case, length maps to the length of the file in bytes.
@@ -75,6 +104,24 @@ struct Info {
 
     ///In the multi file case, a set of files which go in a directory structure.
     files: Option<Vec<FilesInfo>>,
+
+    /// BEP 52: 2 when this info dict follows the v2 (or hybrid) layout,
+    /// implicitly 1 (absent) for the classic v1 layout.
+    meta_version: Option<u32>,
+
+    /// BEP 52: the v2 file tree, present for v2 and hybrid torrents. Flattened
+    /// to one entry per file, each carrying its full path under `name`.
+    file_tree: Option<Vec<FileTreeEntry>>,
+}
+
+/// One file leaf of a BEP 52 v2 `file tree`.
+#[derive(Debug, PartialEq, Clone)]
+struct FileTreeEntry {
+    /// Path components under `name`, e.g. `["subdir", "file.txt"]`.
+    path: Vec<String>,
+    length: usize,
+    /// Root of the file's block-hash Merkle tree (see [`crate::verify`]).
+    pieces_root: [u8; 32],
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -94,27 +141,138 @@ fn parse_torrent(data: &[u8]) -> Result<Torrent, String> {
                 return Err("Trailing bytes after root element".to_string());
             }
 
+            let announce_list = match dict.dict.get(&BString {
+                content: b"announce-list",
+            }) {
+                Some(BencodeValue::List(BList { items })) => Some(parse_announce_list(items)?),
+                None => None,
+                _ => return Err("Invalid announce-list field".to_string()),
+            };
+
             let announce = match dict.dict.get(&BString {
                 content: b"announce",
             }) {
                 Some(BencodeValue::String(BString { content })) => {
                     String::from_utf8(content.to_vec())
+                        .map_err(|_| "Invalid utf-8 bytes in announce".to_string())?
                 }
+                None => announce_list
+                    .as_ref()
+                    .and_then(|tiers| tiers.iter().flatten().next())
+                    .cloned()
+                    .ok_or_else(|| "Invalid or Missing announce url".to_string())?,
                 _ => return Err("Invalid or Missing announce url".to_string()),
-            }
-            .unwrap();
+            };
 
             let info = match dict.dict.get(&BString { content: b"info" }) {
                 Some(BencodeValue::Dictionary(dict)) => parse_torrent_info(dict)?,
                 _ => return Err("Invalid or Missing torrent info field".to_string()),
             };
 
-            Ok(Torrent { announce, info })
+            let info_span = find_top_level_value_span(data, b"info")?
+                .ok_or_else(|| "Invalid or Missing torrent info field".to_string())?;
+            let info_hash = sha1::sha1(info_span);
+            let info_hash_v2 = (info.meta_version == Some(2)).then(|| sha256::sha256(info_span));
+
+            let piece_layers = match dict.dict.get(&BString {
+                content: b"piece layers",
+            }) {
+                Some(BencodeValue::Dictionary(BDictionary { dict: layers })) => {
+                    Some(parse_piece_layers(layers)?)
+                }
+                None => None,
+                _ => return Err("Invalid piece layers field".to_string()),
+            };
+
+            Ok(Torrent {
+                announce,
+                announce_list,
+                info,
+                info_hash,
+                info_hash_v2,
+                piece_layers,
+            })
         }
         _ => Err("Expected root element to be a BDictionary".to_string()),
     }
 }
 
+/// Walks the raw bytes of a top-level bencode dictionary and returns the
+/// exact byte span covered by `key`'s value, without re-encoding it. This is
+/// how the `info` dict's bytes are recovered for hashing: `parse_bencode`
+/// already hands back the slice remaining after each value, so the consumed
+/// span is `&data[start..data.len() - rest.len()]`.
+fn find_top_level_value_span<'a>(data: &'a [u8], key: &[u8]) -> Result<Option<&'a [u8]>, String> {
+    if data.is_empty() || data[0] != b'd' {
+        return Err("Expected root element to be a BDictionary".to_string());
+    }
+
+    let mut rest = &data[1..];
+    while !rest.is_empty() && rest[0] != b'e' {
+        let (after_key, key_value) = parse_bencode_string(rest)?;
+        let found_key = match key_value {
+            BencodeValue::String(BString { content }) => content,
+            _ => return Err("Dictionary key must be BString".to_string()),
+        };
+
+        let value_start = data.len() - after_key.len();
+        let (after_value, _) = parse_bencode(after_key)?;
+        let value_end = data.len() - after_value.len();
+
+        if found_key == key {
+            return Ok(Some(&data[value_start..value_end]));
+        }
+
+        rest = after_value;
+    }
+
+    Ok(None)
+}
+
+/// Parses a BEP 12 `announce-list`: a list of tiers, each a list of tracker
+/// URL strings, preserving tier and within-tier ordering.
+fn parse_announce_list(tiers: &[BencodeValue]) -> Result<Vec<Vec<String>>, String> {
+    tiers
+        .iter()
+        .map(|tier| match tier {
+            BencodeValue::List(BList { items }) => items
+                .iter()
+                .map(|url| match url {
+                    BencodeValue::String(BString { content }) => {
+                        String::from_utf8(content.to_vec())
+                            .map_err(|_| "Invalid utf-8 bytes in announce-list url".to_string())
+                    }
+                    _ => Err("announce-list tier entries must be strings".to_string()),
+                })
+                .collect(),
+            _ => Err("announce-list entries must be lists".to_string()),
+        })
+        .collect()
+}
+
+/// Parses the top-level BEP 52 `piece layers` dict: each key is a 32-byte
+/// `pieces root` and each value the concatenated SHA-256 hashes of that
+/// file's piece layer.
+fn parse_piece_layers(layers: &HashMap<BString, BencodeValue>) -> Result<HashMap<[u8; 32], Vec<u8>>, String> {
+    layers
+        .iter()
+        .map(|(key, value)| {
+            if key.content.len() != 32 {
+                return Err("piece layers key must be a 32-byte pieces root".to_string());
+            }
+            let mut root = [0u8; 32];
+            root.copy_from_slice(key.content);
+
+            let hashes = match value {
+                BencodeValue::String(BString { content }) => content.to_vec(),
+                _ => return Err("piece layers value must be a string".to_string()),
+            };
+
+            Ok((root, hashes))
+        })
+        .collect()
+}
+
 fn parse_torrent_info(info: &BDictionary) -> Result<Info, String> {
     let name = match info.dict.get(&BString { content: b"name" }) {
         Some(BencodeValue::String(BString { content })) => String::from_utf8(content.to_vec())
@@ -129,9 +287,19 @@ fn parse_torrent_info(info: &BDictionary) -> Result<Info, String> {
         _ => return Err("Invalid or Missing info piece length".to_string()),
     };
 
-    // get pieces
+    let meta_version = match info.dict.get(&BString {
+        content: b"meta version",
+    }) {
+        Some(BencodeValue::Integer(BInteger { value })) => Some(*value as u32),
+        None => None,
+        _ => return Err("Invalid meta version".to_string()),
+    };
+    let is_v2 = meta_version == Some(2);
+
+    // get pieces. A pure v2 torrent carries no flat v1 piece list.
     let pieces = match info.dict.get(&BString { content: b"pieces" }) {
         Some(BencodeValue::String(BString { content })) => content.to_vec(),
+        None if is_v2 => Vec::new(),
         _ => return Err("Invalid or Missing pieces info".to_string()),
     };
 
@@ -180,24 +348,103 @@ fn parse_torrent_info(info: &BDictionary) -> Result<Info, String> {
 
                     result.push(FilesInfo { length, path })
                 }
+                Some(result)
             }
+            // A pure v2 torrent describes its files via `file tree` instead.
+            None if is_v2 => None,
             _ => return Err("Missing or Invalid info files".to_string()),
         }
-        Some(result)
     } else {
         None
     };
 
+    let file_tree = match info.dict.get(&BString {
+        content: b"file tree",
+    }) {
+        Some(BencodeValue::Dictionary(tree)) => {
+            let mut entries = parse_file_tree(tree, Vec::new())?;
+            entries.sort_by(|a, b| a.path.cmp(&b.path));
+            Some(entries)
+        }
+        None => None,
+        _ => return Err("Invalid file tree".to_string()),
+    };
+
     Ok(Info {
         name,
         piece_length,
         pieces,
         length,
         files,
+        meta_version,
+        file_tree,
     })
 }
 
+/// Recursively walks a BEP 52 `file tree` dict. Each leaf file is encoded as
+/// `{"": {"length": .., "pieces root": ..}}`: an empty-string key holding the
+/// file's metadata, sitting alongside sibling directory-name keys that hold
+/// nested `file tree` dicts.
+fn parse_file_tree(dict: &BDictionary, prefix: Vec<String>) -> Result<Vec<FileTreeEntry>, String> {
+    let mut entries = Vec::new();
+
+    for (key, value) in &dict.dict {
+        let name = String::from_utf8(key.content.to_vec())
+            .map_err(|_| "Invalid utf-8 bytes in file tree path".to_string())?;
+
+        if name.is_empty() {
+            if prefix.is_empty() {
+                return Err("file tree leaf has no path component".to_string());
+            }
+
+            let leaf = match value {
+                BencodeValue::Dictionary(BDictionary { dict }) => dict,
+                _ => return Err("file tree leaf must be a dictionary".to_string()),
+            };
+
+            let length = match leaf.get(&BString { content: b"length" }) {
+                Some(BencodeValue::Integer(BInteger { value })) => *value as usize,
+                _ => return Err("Missing file tree leaf length".to_string()),
+            };
+
+            let pieces_root = match leaf.get(&BString {
+                content: b"pieces root",
+            }) {
+                Some(BencodeValue::String(BString { content })) if content.len() == 32 => {
+                    let mut root = [0u8; 32];
+                    root.copy_from_slice(content);
+                    root
+                }
+                // Zero-length files have no blocks to hash.
+                None if length == 0 => [0u8; 32],
+                _ => return Err("Missing or invalid pieces root".to_string()),
+            };
+
+            entries.push(FileTreeEntry {
+                path: prefix.clone(),
+                length,
+                pieces_root,
+            });
+        } else {
+            let subtree = match value {
+                BencodeValue::Dictionary(dict) => dict,
+                _ => return Err("file tree entries must be dictionaries".to_string()),
+            };
+
+            let mut child_prefix = prefix.clone();
+            child_prefix.push(name);
+            entries.extend(parse_file_tree(subtree, child_prefix)?);
+        }
+    }
+
+    Ok(entries)
+}
+
 fn parse_bencode(data: &[u8]) -> Result<(&[u8], BencodeValue), String> {
+    if data.is_empty() {
+        return Err("Unexpected end of input".to_string());
+    }
+
     match data[0] {
         b'i' => parse_bencode_integer(&data[1..]),
         b'l' => parse_bencode_list(&data[1..]),
@@ -221,11 +468,15 @@ fn parse_bencode_string(data: &[u8]) -> Result<(&[u8], BencodeValue), String> {
     let len_str = String::from_utf8(data[..i].to_vec())
         .map_err(|err| format!("Failed to get len from string: {err}"))?;
 
+    if len_str.len() > 1 && len_str.starts_with('0') {
+        return Err("BString length has a leading zero".to_string());
+    }
+
     let len = len_str
         .parse::<usize>()
         .map_err(|err| format!("Failed to parse len: {err}"))?;
 
-    if i + 1 + len > data.len() {
+    if len > data.len().saturating_sub(i + 1) {
         return Err("Missing some String bytes".to_string());
     }
 
@@ -244,9 +495,27 @@ fn parse_bencode_integer(data: &[u8]) -> Result<(&[u8], BencodeValue), String> {
         i += 1;
     }
 
+    if i == data.len() {
+        return Err("Unterminated integer".to_string());
+    }
+
     let num_str = String::from_utf8(data[..i].to_vec())
         .map_err(|err| format!("Invalid utf-8 bytes in num: {err}"))?;
 
+    if num_str.is_empty() {
+        return Err("Empty integer".to_string());
+    }
+    if !num_str.bytes().all(|b| b.is_ascii_digit() || b == b'-') {
+        return Err(format!("Invalid character in integer: {num_str}"));
+    }
+    if num_str == "-0" {
+        return Err("Negative zero integer is invalid".to_string());
+    }
+    let digits = num_str.strip_prefix('-').unwrap_or(&num_str);
+    if digits.len() > 1 && digits.starts_with('0') {
+        return Err(format!("Integer has a leading zero: {num_str}"));
+    }
+
     let value = num_str
         .parse::<i64>()
         .map_err(|err| format!("failed to parse num: {err}"))?;
@@ -271,9 +540,288 @@ fn parse_bencode_list(data: &[u8]) -> Result<(&[u8], BencodeValue), String> {
     Ok((&rest[1..], BencodeValue::List(BList { items })))
 }
 
+/// Serializes a `BencodeValue` back into canonical bencode bytes: strings as
+/// `len:bytes`, integers as `i<n>e`, lists as `l...e` and dictionaries as
+/// `d...e` with keys emitted in ascending raw-byte order. Dictionary keys are
+/// sorted here because `BDictionary` is backed by a `HashMap`, so round-tripping
+/// through `parse_bencode` requires re-imposing a deterministic order.
+fn encode(value: &BencodeValue) -> Vec<u8> {
+    match value {
+        BencodeValue::String(BString { content }) => {
+            let mut out = content.len().to_string().into_bytes();
+            out.push(b':');
+            out.extend_from_slice(content);
+            out
+        }
+        BencodeValue::Integer(BInteger { value }) => format!("i{value}e").into_bytes(),
+        BencodeValue::List(BList { items }) => {
+            let mut out = vec![b'l'];
+            for item in items {
+                out.extend(encode(item));
+            }
+            out.push(b'e');
+            out
+        }
+        BencodeValue::Dictionary(BDictionary { dict }) => {
+            let mut entries: Vec<_> = dict.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.content.cmp(b.content));
+
+            let mut out = vec![b'd'];
+            for (key, value) in entries {
+                out.extend(encode(&BencodeValue::String(key.clone())));
+                out.extend(encode(value));
+            }
+            out.push(b'e');
+            out
+        }
+    }
+}
+
+impl Torrent {
+    /// Serializes the metainfo back into canonical bencode bytes.
+    fn encode(&self) -> Vec<u8> {
+        encode(&self.to_bencode_value())
+    }
+
+    /// The info-hash as a lowercase hex string.
+    fn info_hash_hex(&self) -> String {
+        sha1::hex(&self.info_hash)
+    }
+
+    /// The v2 info-hash as a lowercase hex string, for v2/hybrid torrents.
+    fn info_hash_v2_hex(&self) -> Option<String> {
+        self.info_hash_v2.map(|hash| sha256::hex(&hash))
+    }
+
+    fn to_bencode_value(&self) -> BencodeValue {
+        let mut dict = HashMap::new();
+        dict.insert(
+            BString {
+                content: b"announce",
+            },
+            BencodeValue::String(BString {
+                content: self.announce.as_bytes(),
+            }),
+        );
+        dict.insert(BString { content: b"info" }, self.info.to_bencode_value());
+
+        if let Some(tiers) = &self.announce_list {
+            let items = tiers
+                .iter()
+                .map(|tier| {
+                    let urls = tier
+                        .iter()
+                        .map(|url| BencodeValue::String(BString { content: url.as_bytes() }))
+                        .collect();
+                    BencodeValue::List(BList { items: urls })
+                })
+                .collect();
+            dict.insert(
+                BString {
+                    content: b"announce-list",
+                },
+                BencodeValue::List(BList { items }),
+            );
+        }
+
+        if let Some(layers) = &self.piece_layers {
+            let layers_dict = layers
+                .iter()
+                .map(|(root, hashes)| {
+                    (
+                        BString {
+                            content: root.as_slice(),
+                        },
+                        BencodeValue::String(BString { content: hashes }),
+                    )
+                })
+                .collect();
+            dict.insert(
+                BString {
+                    content: b"piece layers",
+                },
+                BencodeValue::Dictionary(BDictionary { dict: layers_dict }),
+            );
+        }
+
+        BencodeValue::Dictionary(BDictionary { dict })
+    }
+}
+
+impl Info {
+    /// Total size of the torrent's content in bytes, from `length` in the
+    /// single-file case or the sum of `files` in the multi-file case.
+    fn total_length(&self) -> usize {
+        match (&self.length, &self.files, &self.file_tree) {
+            (Some(length), _, _) => *length,
+            (None, Some(files), _) => files.iter().map(|file| file.length).sum(),
+            (None, None, Some(file_tree)) => file_tree.iter().map(|entry| entry.length).sum(),
+            (None, None, None) => 0,
+        }
+    }
+
+    fn to_bencode_value(&self) -> BencodeValue {
+        let mut dict = HashMap::new();
+        dict.insert(
+            BString { content: b"name" },
+            BencodeValue::String(BString {
+                content: self.name.as_bytes(),
+            }),
+        );
+        dict.insert(
+            BString {
+                content: b"piece length",
+            },
+            BencodeValue::Integer(BInteger {
+                value: self.piece_length as i64,
+            }),
+        );
+        if !self.pieces.is_empty() {
+            dict.insert(
+                BString {
+                    content: b"pieces",
+                },
+                BencodeValue::String(BString {
+                    content: &self.pieces,
+                }),
+            );
+        }
+
+        if let Some(length) = self.length {
+            dict.insert(
+                BString {
+                    content: b"length",
+                },
+                BencodeValue::Integer(BInteger {
+                    value: length as i64,
+                }),
+            );
+        }
+
+        if let Some(files) = &self.files {
+            let items = files.iter().map(FilesInfo::to_bencode_value).collect();
+            dict.insert(
+                BString {
+                    content: b"files",
+                },
+                BencodeValue::List(BList { items }),
+            );
+        }
+
+        if let Some(meta_version) = self.meta_version {
+            dict.insert(
+                BString {
+                    content: b"meta version",
+                },
+                BencodeValue::Integer(BInteger {
+                    value: meta_version as i64,
+                }),
+            );
+        }
+
+        if let Some(file_tree) = &self.file_tree {
+            let refs: Vec<&FileTreeEntry> = file_tree.iter().collect();
+            dict.insert(
+                BString {
+                    content: b"file tree",
+                },
+                build_file_tree(&refs, 0),
+            );
+        }
+
+        BencodeValue::Dictionary(BDictionary { dict })
+    }
+}
+
+/// Rebuilds a BEP 52 `file tree` dict from the flattened `FileTreeEntry`
+/// list, grouping entries that share a path prefix at `depth` under that
+/// component and recursing until each group bottoms out at a single leaf.
+fn build_file_tree<'a>(entries: &[&'a FileTreeEntry], depth: usize) -> BencodeValue<'a> {
+    let mut order: Vec<&'a str> = Vec::new();
+    let mut groups: HashMap<&'a str, Vec<&'a FileTreeEntry>> = HashMap::new();
+
+    for &entry in entries {
+        let component = entry.path[depth].as_str();
+        groups.entry(component).or_default().push(entry);
+        if !order.contains(&component) {
+            order.push(component);
+        }
+    }
+
+    let mut dict = HashMap::new();
+    for component in order {
+        let group = &groups[component];
+        let value = if depth + 1 == group[0].path.len() {
+            let leaf_entry = group[0];
+            let mut leaf = HashMap::new();
+            leaf.insert(
+                BString {
+                    content: b"length",
+                },
+                BencodeValue::Integer(BInteger {
+                    value: leaf_entry.length as i64,
+                }),
+            );
+            leaf.insert(
+                BString {
+                    content: b"pieces root",
+                },
+                BencodeValue::String(BString {
+                    content: &leaf_entry.pieces_root,
+                }),
+            );
+
+            let mut outer = HashMap::new();
+            outer.insert(
+                BString { content: b"" },
+                BencodeValue::Dictionary(BDictionary { dict: leaf }),
+            );
+            BencodeValue::Dictionary(BDictionary { dict: outer })
+        } else {
+            build_file_tree(group, depth + 1)
+        };
+
+        dict.insert(
+            BString {
+                content: component.as_bytes(),
+            },
+            value,
+        );
+    }
+
+    BencodeValue::Dictionary(BDictionary { dict })
+}
+
+impl FilesInfo {
+    fn to_bencode_value(&self) -> BencodeValue {
+        let mut dict = HashMap::new();
+        dict.insert(
+            BString {
+                content: b"length",
+            },
+            BencodeValue::Integer(BInteger {
+                value: self.length as i64,
+            }),
+        );
+
+        let items = self
+            .path
+            .iter()
+            .map(|component| BencodeValue::String(BString { content: component.as_bytes() }))
+            .collect();
+        dict.insert(
+            BString { content: b"path" },
+            BencodeValue::List(BList { items }),
+        );
+
+        BencodeValue::Dictionary(BDictionary { dict })
+    }
+}
+
 fn parse_bencode_dictionary(data: &[u8]) -> Result<(&[u8], BencodeValue), String> {
     let mut rest = data;
     let mut map = HashMap::new();
+    let mut prev_key: Option<BString> = None;
 
     while !rest.is_empty() && rest[0] != b'e' {
         //parse key
@@ -284,8 +832,28 @@ fn parse_bencode_dictionary(data: &[u8]) -> Result<(&[u8], BencodeValue), String
             _ => return Err("Dictionary key must be BString".to_string()),
         };
 
+        if let Some(prev) = &prev_key {
+            match prev.content.cmp(key.content) {
+                std::cmp::Ordering::Less => {}
+                std::cmp::Ordering::Equal => {
+                    return Err(format!(
+                        "Dictionary has a duplicate key: {}",
+                        String::from_utf8_lossy(key.content)
+                    ));
+                }
+                std::cmp::Ordering::Greater => {
+                    return Err(format!(
+                        "Dictionary keys are not in ascending order: {} before {}",
+                        String::from_utf8_lossy(prev.content),
+                        String::from_utf8_lossy(key.content)
+                    ));
+                }
+            }
+        }
+
         let (new_rest, value) = parse_bencode(new_rest)?;
         rest = new_rest;
+        prev_key = Some(key.clone());
         map.insert(key, value);
     }
 
@@ -300,12 +868,122 @@ fn parse_bencode_dictionary(data: &[u8]) -> Result<(&[u8], BencodeValue), String
 }
 
 fn main() -> Result<(), String> {
-    let torrent = b"d8:announce11:example.com4:infod4:name9:blindspot12:piece lengthi20e6:pieces5:hello5:filesld6:lengthi10e4:pathl5:path15:path2eeeee";
-    let torrent = parse_torrent(torrent)?;
-    println!("{:?}", torrent);
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("show") => cmd_show(&args[2..]),
+        Some("verify") => cmd_verify(&args[2..]),
+        Some("announce") => cmd_announce(&args[2..]),
+        Some("magnet") => cmd_magnet(&args[2..]),
+        _ => Err(format!(
+            "usage: {} <show|verify|announce|magnet> ...",
+            args.first().map(String::as_str).unwrap_or("btrust")
+        )),
+    }
+}
+
+/// `show <torrent-file>`: parses a `.torrent` file and prints its metadata,
+/// info-hash(es), and magnet link. Also re-encodes it with `Torrent::encode`
+/// and prints the size of the result, to exercise the encoder alongside the
+/// parser.
+fn cmd_show(args: &[String]) -> Result<(), String> {
+    let [path] = args else {
+        return Err("usage: show <torrent-file>".to_string());
+    };
+
+    let torrent = read_torrent(path)?;
+
+    println!("announce: {}", torrent.announce);
+    println!("name: {}", torrent.info.name);
+    println!("info-hash: {}", torrent.info_hash_hex());
+    if let Some(hash) = torrent.info_hash_v2_hex() {
+        println!("info-hash (v2): {hash}");
+    }
+    println!("magnet: {}", magnet::to_magnet_uri(&torrent));
+    println!("re-encoded: {} bytes", torrent.encode().len());
+
+    Ok(())
+}
+
+/// `verify <torrent-file> <content-dir>`: checks on-disk content against the
+/// torrent's piece hashes (v1) and/or per-file `pieces root` (v2/hybrid).
+fn cmd_verify(args: &[String]) -> Result<(), String> {
+    let [path, root] = args else {
+        return Err("usage: verify <torrent-file> <content-dir>".to_string());
+    };
+
+    let torrent = read_torrent(path)?;
+    let root = std::path::Path::new(root);
+
+    if !torrent.info.pieces.is_empty() {
+        let report = verify::verify(&torrent, root);
+        println!("v1 complete: {}", report.is_complete());
+        for piece in &report.pieces {
+            println!("  piece {}: {:?}", piece.index, piece.status);
+        }
+    }
+
+    for file in verify::verify_v2(&torrent, root) {
+        println!("v2 {}: {:?}", file.path.display(), file.status);
+    }
+
+    Ok(())
+}
+
+/// `announce <torrent-file>`: performs a single BEP 3 announce and prints
+/// the peers the tracker returned.
+fn cmd_announce(args: &[String]) -> Result<(), String> {
+    let [path] = args else {
+        return Err("usage: announce <torrent-file>".to_string());
+    };
+
+    let torrent = read_torrent(path)?;
+    let request = tracker::AnnounceRequest {
+        peer_id: tracker::generate_peer_id(),
+        port: 6881,
+        uploaded: 0,
+        downloaded: 0,
+        left: None,
+    };
+
+    let response = tracker::announce(&torrent, &request)
+        .map_err(|err| format!("announce failed: {err:?}"))?;
+    println!("interval: {}", response.interval);
+    for peer in &response.peers {
+        println!("peer: {peer}");
+    }
+
     Ok(())
 }
 
+/// `magnet <torrent-file>`: prints the magnet link for a `.torrent` file, or
+/// `magnet parse <uri>` to decode one back into its info-hash and metadata.
+fn cmd_magnet(args: &[String]) -> Result<(), String> {
+    match args {
+        [uri_or_path] => {
+            let torrent = read_torrent(uri_or_path)?;
+            println!("{}", magnet::to_magnet_uri(&torrent));
+            Ok(())
+        }
+        [cmd, uri] if cmd.as_str() == "parse" => {
+            let link = magnet::parse_magnet(uri).map_err(|err| format!("{err:?}"))?;
+            println!("info-hash: {}", sha1::hex(&link.info_hash));
+            if let Some(name) = &link.display_name {
+                println!("name: {name}");
+            }
+            for url in &link.trackers {
+                println!("tracker: {url}");
+            }
+            Ok(())
+        }
+        _ => Err("usage: magnet <torrent-file> | magnet parse <uri>".to_string()),
+    }
+}
+
+fn read_torrent(path: &str) -> Result<Torrent, String> {
+    let data = std::fs::read(path).map_err(|err| format!("reading {path}: {err}"))?;
+    parse_torrent(&data)
+}
+
 #[cfg(test)]
 mod test {
     mod bencoding {
@@ -421,37 +1099,175 @@ mod test {
                 })
             )
         }
+
+        #[test]
+        fn rejects_empty_input() {
+            assert!(parse_bencode(b"").is_err());
+        }
+
+        #[test]
+        fn rejects_integer_with_leading_zero() {
+            assert!(parse_bencode(b"i03e").is_err());
+        }
+
+        #[test]
+        fn rejects_negative_zero_integer() {
+            assert!(parse_bencode(b"i-0e").is_err());
+        }
+
+        #[test]
+        fn rejects_empty_integer() {
+            assert!(parse_bencode(b"ie").is_err());
+        }
+
+        #[test]
+        fn rejects_non_digit_integer() {
+            assert!(parse_bencode(b"i5a6e").is_err());
+        }
+
+        #[test]
+        fn rejects_unterminated_integer() {
+            assert!(parse_bencode(b"i56").is_err());
+        }
+
+        #[test]
+        fn rejects_string_length_with_leading_zero() {
+            assert!(parse_bencode(b"03:foo").is_err());
+        }
+
+        #[test]
+        fn rejects_dictionary_keys_out_of_order() {
+            // {"mike": "angela", "a": "foo"} with keys swapped from canonical order
+            assert!(parse_bencode(b"d4:mike6:angela1:a3:fooe").is_err());
+        }
+
+        #[test]
+        fn rejects_duplicate_dictionary_keys() {
+            assert!(parse_bencode(b"d1:a3:foo1:a3:bare").is_err());
+        }
+
+        #[test]
+        fn rejects_string_length_overflow_instead_of_panicking() {
+            assert!(parse_bencode(b"18446744073709551615:x").is_err());
+        }
+    }
+
+    mod encoding {
+        use crate::*;
+
+        #[test]
+        fn integer() {
+            let value = BencodeValue::Integer(BInteger { value: 56 });
+            assert_eq!(encode(&value), b"i56e");
+        }
+
+        #[test]
+        fn negative_integer() {
+            let value = BencodeValue::Integer(BInteger { value: -56 });
+            assert_eq!(encode(&value), b"i-56e");
+        }
+
+        #[test]
+        fn zero() {
+            let value = BencodeValue::Integer(BInteger { value: 0 });
+            assert_eq!(encode(&value), b"i0e");
+        }
+
+        #[test]
+        fn string() {
+            let value = BencodeValue::String(BString { content: b"foo" });
+            assert_eq!(encode(&value), b"3:foo");
+        }
+
+        #[test]
+        fn list() {
+            let value = BencodeValue::List(BList {
+                items: [
+                    BencodeValue::String(BString { content: b"foo" }),
+                    BencodeValue::String(BString { content: b"bar" }),
+                ]
+                .to_vec(),
+            });
+            assert_eq!(encode(&value), b"l3:foo3:bare");
+        }
+
+        #[test]
+        fn dictionary_keys_are_sorted() {
+            // {"mike": "angela", "a": "foo"} must encode with "a" first,
+            // regardless of HashMap iteration order.
+            let value = BencodeValue::Dictionary(BDictionary {
+                dict: HashMap::from([
+                    (
+                        BString { content: b"mike" },
+                        BencodeValue::String(BString { content: b"angela" }),
+                    ),
+                    (
+                        BString { content: b"a" },
+                        BencodeValue::String(BString { content: b"foo" }),
+                    ),
+                ]),
+            });
+            assert_eq!(encode(&value), b"d1:a3:foo4:mike6:angelae");
+        }
+
+        #[test]
+        fn round_trips_through_parse_bencode() {
+            let original: &[u8] = b"d3:foo3:bar4:listl6:angela5:jamesee";
+            let (rest, value) = parse_bencode(original).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(encode(&value), original);
+        }
+
+        #[test]
+        fn torrent_round_trips() {
+            // Keys within each dictionary are already in canonical
+            // ascending-byte order so the encoded bytes match exactly.
+            let canonical: &[u8] =
+                b"d8:announce11:example.com4:infod6:lengthi10e4:name9:blindspot12:piece lengthi20e6:pieces5:helloee";
+            let torrent = parse_torrent(canonical).unwrap();
+            assert_eq!(torrent.encode(), canonical);
+        }
     }
 
     mod torrent {
         use crate::*;
         #[test]
         fn simple() {
-            let torrent = b"d8:announce11:example.com4:infod4:name9:blindspot12:piece lengthi20e6:pieces5:hello6:lengthi10eee";
+            let torrent = b"d8:announce11:example.com4:infod6:lengthi10e4:name9:blindspot12:piece lengthi20e6:pieces5:helloee";
             let torrent = parse_torrent(torrent).unwrap();
             assert_eq!(
                 torrent,
                 Torrent {
                     announce: "example.com".to_string(),
+                    announce_list: None,
                     info: Info {
                         name: "blindspot".to_string(),
                         piece_length: 20,
                         pieces: [104, 101, 108, 108, 111].to_vec(),
                         length: Some(10),
-                        files: None
-                    }
+                        files: None,
+                        meta_version: None,
+                        file_tree: None
+                    },
+                    info_hash: [
+                        50, 206, 177, 139, 135, 252, 135, 12, 177, 10, 197, 163, 71, 45, 183, 178,
+                        249, 116, 96, 33
+                    ],
+                    info_hash_v2: None,
+                    piece_layers: None
                 }
             )
         }
 
         #[test]
         fn complex() {
-            let torrent = b"d8:announce11:example.com4:infod4:name9:blindspot12:piece lengthi20e6:pieces5:hello5:filesld6:lengthi10e4:pathl5:path15:path2eeeee";
+            let torrent = b"d8:announce11:example.com4:infod5:filesld6:lengthi10e4:pathl5:path15:path2eee4:name9:blindspot12:piece lengthi20e6:pieces5:helloee";
             let torrent = parse_torrent(torrent).unwrap();
             assert_eq!(
                 torrent,
                 Torrent {
                     announce: "example.com".to_string(),
+                    announce_list: None,
                     info: Info {
                         name: "blindspot".to_string(),
                         piece_length: 20,
@@ -463,10 +1279,107 @@ mod test {
                                 path: ["path1".to_string(), "path2".to_string()].to_vec()
                             }]
                             .to_vec()
-                        )
-                    }
+                        ),
+                        meta_version: None,
+                        file_tree: None
+                    },
+                    info_hash: [
+                        2, 241, 197, 170, 180, 33, 195, 43, 95, 98, 245, 154, 173, 124, 246, 35,
+                        222, 250, 13, 229
+                    ],
+                    info_hash_v2: None,
+                    piece_layers: None
                 }
             )
         }
+
+        #[test]
+        fn info_hash_hex_is_lowercase_sha1_of_info_dict() {
+            let torrent = b"d8:announce11:example.com4:infod6:lengthi10e4:name9:blindspot12:piece lengthi20e6:pieces5:helloee";
+            let torrent = parse_torrent(torrent).unwrap();
+            assert_eq!(
+                torrent.info_hash_hex(),
+                "32ceb18b87fc870cb10ac5a3472db7b2f9746021"
+            );
+        }
+
+        #[test]
+        fn parses_announce_list_tiers_in_order() {
+            let torrent = b"d8:announce11:example.com13:announce-listll8:http://a8:http://bel8:http://cee4:infod6:lengthi10e4:name9:blindspot12:piece lengthi20e6:pieces5:helloee";
+            let torrent = parse_torrent(torrent).unwrap();
+            assert_eq!(
+                torrent.announce_list,
+                Some(vec![
+                    vec!["http://a".to_string(), "http://b".to_string()],
+                    vec!["http://c".to_string()],
+                ])
+            );
+        }
+
+        #[test]
+        fn falls_back_to_first_announce_list_entry_when_announce_is_missing() {
+            let torrent = b"d13:announce-listll8:http://a8:http://bel8:http://cee4:infod6:lengthi10e4:name9:blindspot12:piece lengthi20e6:pieces5:helloee";
+            let torrent = parse_torrent(torrent).unwrap();
+            assert_eq!(torrent.announce, "http://a");
+        }
+
+        #[test]
+        fn missing_announce_and_announce_list_is_an_error() {
+            let torrent = b"d4:infod6:lengthi10e4:name9:blindspot12:piece lengthi20e6:pieces5:helloee";
+            assert!(parse_torrent(torrent).is_err());
+        }
+    }
+
+    mod torrent_v2 {
+        use crate::*;
+
+        const V2_TORRENT: &[u8] = b"\x64\x38\x3a\x61\x6e\x6e\x6f\x75\x6e\x63\x65\x31\x31\x3a\x65\x78\x61\x6d\x70\x6c\x65\x2e\x63\x6f\x6d\x34\x3a\x69\x6e\x66\x6f\x64\x39\x3a\x66\x69\x6c\x65\x20\x74\x72\x65\x65\x64\x38\x3a\x66\x69\x6c\x65\x2e\x62\x69\x6e\x64\x30\x3a\x64\x36\x3a\x6c\x65\x6e\x67\x74\x68\x69\x31\x31\x65\x31\x31\x3a\x70\x69\x65\x63\x65\x73\x20\x72\x6f\x6f\x74\x33\x32\x3a\xb9\x4d\x27\xb9\x93\x4d\x3e\x08\xa5\x2e\x52\xd7\xda\x7d\xab\xfa\xc4\x84\xef\xe3\x7a\x53\x80\xee\x90\x88\xf7\xac\xe2\xef\xcd\xe9\x65\x65\x65\x31\x32\x3a\x6d\x65\x74\x61\x20\x76\x65\x72\x73\x69\x6f\x6e\x69\x32\x65\x34\x3a\x6e\x61\x6d\x65\x35\x3a\x76\x32\x64\x69\x72\x31\x32\x3a\x70\x69\x65\x63\x65\x20\x6c\x65\x6e\x67\x74\x68\x69\x31\x36\x33\x38\x34\x65\x65\x65";
+
+        #[test]
+        fn parses_v2_file_tree_and_meta_version() {
+            let torrent = parse_torrent(V2_TORRENT).unwrap();
+            assert_eq!(torrent.info.meta_version, Some(2));
+            assert_eq!(torrent.info.pieces, Vec::<u8>::new());
+            assert_eq!(torrent.info.length, None);
+            assert_eq!(torrent.info.files, None);
+
+            let file_tree = torrent.info.file_tree.as_ref().unwrap();
+            assert_eq!(file_tree.len(), 1);
+            assert_eq!(file_tree[0].path, vec!["file.bin".to_string()]);
+            assert_eq!(file_tree[0].length, 11);
+        }
+
+        #[test]
+        fn computes_v2_info_hash() {
+            let torrent = parse_torrent(V2_TORRENT).unwrap();
+            assert_eq!(
+                torrent.info_hash_v2_hex(),
+                Some("eadfe89cfe88fd0123f1877cb0cf0532bb0c5842ce9b16c3bbe93e15141a6190".to_string())
+            );
+        }
+
+        #[test]
+        fn v1_only_torrent_has_no_v2_info_hash() {
+            let torrent =
+                parse_torrent(b"d8:announce11:example.com4:infod6:lengthi10e4:name9:blindspot12:piece lengthi20e6:pieces5:helloee")
+                    .unwrap();
+            assert_eq!(torrent.info_hash_v2_hex(), None);
+        }
+
+        #[test]
+        fn round_trips_through_encode() {
+            let torrent = parse_torrent(V2_TORRENT).unwrap();
+            assert_eq!(torrent.encode(), V2_TORRENT);
+        }
+
+        #[test]
+        fn rejects_file_tree_leaf_with_no_path_component() {
+            let pieces_root = [0u8; 32];
+            let torrent = format!(
+                "d8:announce11:example.com4:infod9:file treed0:d6:lengthi5e11:pieces root32:{}ee12:meta versioni2e4:name5:blind12:piece lengthi16384e6:pieces0:ee",
+                String::from_utf8_lossy(&pieces_root)
+            );
+            assert!(parse_torrent(torrent.as_bytes()).is_err());
+        }
     }
 }
@@ -0,0 +1,410 @@
+//! BEP 3 HTTP tracker announce client.
+//!
+//! Performs the GET announce request against `Torrent::announce`, decodes
+//! the bencoded response with `parse_bencode`, and hands back the peer
+//! list in both the dictionary and BEP 23 compact forms.
+
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
+use std::time::Duration;
+
+use crate::{BDictionary, BInteger, BList, BString, BencodeValue, Torrent, parse_bencode};
+
+/// Parameters for a single announce call that the caller, not the tracker
+/// client, is responsible for tracking across the session.
+pub struct AnnounceRequest {
+    pub peer_id: [u8; 20],
+    pub port: u16,
+    pub uploaded: u64,
+    pub downloaded: u64,
+    /// Bytes left to download. `None` defaults to the torrent's total
+    /// content length (i.e. nothing downloaded yet); a caller tracking
+    /// progress across the session should pass `Some(total - downloaded)`.
+    pub left: Option<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AnnounceResponse {
+    pub interval: u64,
+    pub complete: Option<u64>,
+    pub incomplete: Option<u64>,
+    pub peers: Vec<SocketAddr>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TrackerError {
+    Url(String),
+    Io(String),
+    Bencode(String),
+    /// The tracker rejected the request, carrying its `failure reason`.
+    Failure(String),
+}
+
+/// Generates a 20-byte Azureus-style peer id (`-BT0001-` followed by random
+/// bytes). Good enough to identify this client uniquely to a tracker; not
+/// intended to be cryptographically unpredictable.
+pub fn generate_peer_id() -> [u8; 20] {
+    let mut id = [0u8; 20];
+    id[..8].copy_from_slice(b"-BT0001-");
+
+    let seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(1)
+        ^ (std::process::id() as u64);
+    let mut state = seed | 1;
+    for byte in &mut id[8..] {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        *byte = (state & 0xff) as u8;
+    }
+
+    id
+}
+
+/// Performs the BEP 3 GET announce against `torrent.announce` and returns
+/// the parsed tracker response.
+pub fn announce(
+    torrent: &Torrent,
+    request: &AnnounceRequest,
+) -> Result<AnnounceResponse, TrackerError> {
+    let url = parse_http_url(&torrent.announce)?;
+    let target = format!(
+        "{}{}{}",
+        url.path,
+        if url.path.contains('?') { '&' } else { '?' },
+        build_query(torrent, request)
+    );
+
+    let mut stream = TcpStream::connect((url.host.as_str(), url.port))
+        .map_err(|err| TrackerError::Io(format!("connecting to {}:{}: {err}", url.host, url.port)))?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(15)))
+        .map_err(|err| TrackerError::Io(err.to_string()))?;
+
+    let http_request =
+        format!("GET {target} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nUser-Agent: btrust/0.1\r\n\r\n", url.host);
+    stream
+        .write_all(http_request.as_bytes())
+        .map_err(|err| TrackerError::Io(err.to_string()))?;
+
+    let mut raw = Vec::new();
+    stream
+        .read_to_end(&mut raw)
+        .map_err(|err| TrackerError::Io(err.to_string()))?;
+
+    parse_announce_response(http_body(&raw)?)
+}
+
+/// The pieces of an `http://` tracker URL relevant to building the request.
+/// `https` is not supported; a query string already in the URL stays in
+/// `path` and the announce parameters are appended after it.
+struct Url {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+fn parse_http_url(raw: &str) -> Result<Url, TrackerError> {
+    let rest = raw
+        .strip_prefix("http://")
+        .ok_or_else(|| TrackerError::Url(format!("unsupported tracker scheme: {raw}")))?;
+
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host.to_string(),
+            port.parse::<u16>()
+                .map_err(|_| TrackerError::Url(format!("invalid port in tracker url: {raw}")))?,
+        ),
+        None => (authority.to_string(), 80),
+    };
+
+    Ok(Url {
+        host,
+        port,
+        path: path.to_string(),
+    })
+}
+
+/// Percent-encodes raw bytes for use in a query string, per BEP 3: letters,
+/// digits and `-_.~` pass through unescaped, everything else becomes `%XX`.
+/// Shared with [`crate::magnet`], whose `tr`/`dn` parameters follow the same
+/// rule.
+pub(crate) fn percent_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 3);
+    for &byte in bytes {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+fn build_query(torrent: &Torrent, request: &AnnounceRequest) -> String {
+    let left = request
+        .left
+        .unwrap_or_else(|| torrent.info.total_length() as u64);
+    format!(
+        "info_hash={}&peer_id={}&port={}&uploaded={}&downloaded={}&left={}&compact=1",
+        percent_encode(&torrent.info_hash),
+        percent_encode(&request.peer_id),
+        request.port,
+        request.uploaded,
+        request.downloaded,
+        left,
+    )
+}
+
+fn http_body(raw: &[u8]) -> Result<&[u8], TrackerError> {
+    let separator = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| TrackerError::Io("malformed HTTP response: no header/body separator".to_string()))?;
+    Ok(&raw[separator + 4..])
+}
+
+fn parse_announce_response(body: &[u8]) -> Result<AnnounceResponse, TrackerError> {
+    let (_, value) = parse_bencode(body).map_err(TrackerError::Bencode)?;
+    let dict = match value {
+        BencodeValue::Dictionary(BDictionary { dict }) => dict,
+        _ => return Err(TrackerError::Bencode("tracker response must be a dictionary".to_string())),
+    };
+
+    if let Some(BencodeValue::String(BString { content })) =
+        dict.get(&BString { content: b"failure reason" })
+    {
+        return Err(TrackerError::Failure(
+            String::from_utf8_lossy(content).into_owned(),
+        ));
+    }
+
+    let interval = match dict.get(&BString { content: b"interval" }) {
+        Some(BencodeValue::Integer(BInteger { value })) => *value as u64,
+        _ => return Err(TrackerError::Bencode("missing or invalid interval".to_string())),
+    };
+
+    let complete = match dict.get(&BString { content: b"complete" }) {
+        Some(BencodeValue::Integer(BInteger { value })) => Some(*value as u64),
+        _ => None,
+    };
+    let incomplete = match dict.get(&BString { content: b"incomplete" }) {
+        Some(BencodeValue::Integer(BInteger { value })) => Some(*value as u64),
+        _ => None,
+    };
+
+    let peers = match dict.get(&BString { content: b"peers" }) {
+        Some(BencodeValue::String(BString { content })) => parse_compact_peers(content)?,
+        Some(BencodeValue::List(BList { items })) => parse_peer_list(items)?,
+        _ => return Err(TrackerError::Bencode("missing or invalid peers field".to_string())),
+    };
+
+    Ok(AnnounceResponse {
+        interval,
+        complete,
+        incomplete,
+        peers,
+    })
+}
+
+/// BEP 23 compact peer list: a byte string of 6-byte records, 4-byte IPv4
+/// address followed by a 2-byte big-endian port.
+fn parse_compact_peers(bytes: &[u8]) -> Result<Vec<SocketAddr>, TrackerError> {
+    if !bytes.len().is_multiple_of(6) {
+        return Err(TrackerError::Bencode(
+            "compact peers string length is not a multiple of 6".to_string(),
+        ));
+    }
+
+    Ok(bytes
+        .chunks(6)
+        .map(|chunk| {
+            let ip = Ipv4Addr::new(chunk[0], chunk[1], chunk[2], chunk[3]);
+            let port = u16::from_be_bytes([chunk[4], chunk[5]]);
+            SocketAddr::from((ip, port))
+        })
+        .collect())
+}
+
+/// The original dictionary-model peer list: `[{ip, port, peer id}, ...]`.
+fn parse_peer_list(items: &[BencodeValue]) -> Result<Vec<SocketAddr>, TrackerError> {
+    items
+        .iter()
+        .map(|item| {
+            let BencodeValue::Dictionary(BDictionary { dict }) = item else {
+                return Err(TrackerError::Bencode(
+                    "peer list entries must be dictionaries".to_string(),
+                ));
+            };
+
+            let ip = match dict.get(&BString { content: b"ip" }) {
+                Some(BencodeValue::String(BString { content })) => {
+                    String::from_utf8(content.to_vec())
+                        .map_err(|_| TrackerError::Bencode("invalid utf-8 in peer ip".to_string()))?
+                }
+                _ => return Err(TrackerError::Bencode("missing peer ip".to_string())),
+            };
+            let port = match dict.get(&BString { content: b"port" }) {
+                Some(BencodeValue::Integer(BInteger { value })) => *value as u16,
+                _ => return Err(TrackerError::Bencode("missing peer port".to_string())),
+            };
+
+            let ip: IpAddr = ip
+                .parse()
+                .map_err(|_| TrackerError::Bencode(format!("invalid peer ip: {ip}")))?;
+            Ok(SocketAddr::new(ip, port))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Info;
+
+    #[test]
+    fn percent_encodes_non_unreserved_bytes() {
+        assert_eq!(percent_encode(b"abc123-_.~"), "abc123-_.~");
+        assert_eq!(percent_encode(&[0x00, 0xff, b' ']), "%00%FF%20");
+    }
+
+    #[test]
+    fn parses_host_port_and_path() {
+        let url = parse_http_url("http://tracker.example.com:6969/announce").unwrap();
+        assert_eq!(url.host, "tracker.example.com");
+        assert_eq!(url.port, 6969);
+        assert_eq!(url.path, "/announce");
+    }
+
+    #[test]
+    fn defaults_to_port_80_and_root_path() {
+        let url = parse_http_url("http://tracker.example.com").unwrap();
+        assert_eq!(url.host, "tracker.example.com");
+        assert_eq!(url.port, 80);
+        assert_eq!(url.path, "/");
+    }
+
+    #[test]
+    fn rejects_unsupported_scheme() {
+        assert!(parse_http_url("udp://tracker.example.com:80").is_err());
+    }
+
+    #[test]
+    fn parses_compact_peer_list() {
+        let bytes = [127, 0, 0, 1, 0x1A, 0xE1, 10, 0, 0, 1, 0x1A, 0xE2];
+        let peers = parse_compact_peers(&bytes).unwrap();
+        assert_eq!(
+            peers,
+            vec![
+                SocketAddr::from((Ipv4Addr::new(127, 0, 0, 1), 6881)),
+                SocketAddr::from((Ipv4Addr::new(10, 0, 0, 1), 6882)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_misaligned_compact_peer_list() {
+        assert!(parse_compact_peers(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn parses_compact_announce_response() {
+        let body = b"d8:intervali1800e5:peers12:\x7f\x00\x00\x01\x1a\xe1\x0a\x00\x00\x01\x1a\xe2e";
+        let response = parse_announce_response(body).unwrap();
+        assert_eq!(response.interval, 1800);
+        assert_eq!(response.peers.len(), 2);
+    }
+
+    #[test]
+    fn surfaces_failure_reason_as_an_error() {
+        let body = b"d14:failure reason17:unregistered infoe";
+        let err = parse_announce_response(body).unwrap_err();
+        assert_eq!(err, TrackerError::Failure("unregistered info".to_string()));
+    }
+
+    #[test]
+    fn extracts_body_after_http_headers() {
+        let raw = b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\nd8:intervali1800ee";
+        assert_eq!(http_body(raw).unwrap(), b"d8:intervali1800ee");
+    }
+
+    fn torrent_with_length(length: usize) -> Torrent {
+        Torrent {
+            announce: "http://tracker.example.com/announce".to_string(),
+            announce_list: None,
+            info: Info {
+                name: "blindspot".to_string(),
+                piece_length: 16384,
+                pieces: b"01234567890123456789".to_vec(),
+                length: Some(length),
+                files: None,
+                meta_version: None,
+                file_tree: None,
+            },
+            info_hash: [0xab; 20],
+            info_hash_v2: None,
+            piece_layers: None,
+        }
+    }
+
+    #[test]
+    fn defaults_left_to_total_length_when_unset() {
+        let torrent = torrent_with_length(12345);
+        let request = AnnounceRequest {
+            peer_id: [0u8; 20],
+            port: 6881,
+            uploaded: 0,
+            downloaded: 0,
+            left: None,
+        };
+        assert!(build_query(&torrent, &request).contains("&left=12345&"));
+    }
+
+    #[test]
+    fn caller_supplied_left_overrides_total_length() {
+        let torrent = torrent_with_length(12345);
+        let request = AnnounceRequest {
+            peer_id: [0u8; 20],
+            port: 6881,
+            uploaded: 0,
+            downloaded: 100,
+            left: Some(100),
+        };
+        assert!(build_query(&torrent, &request).contains("&left=100&"));
+    }
+
+    #[test]
+    fn defaults_left_to_summed_file_tree_for_v2_only_torrent() {
+        let mut torrent = torrent_with_length(0);
+        torrent.info.length = None;
+        torrent.info.meta_version = Some(2);
+        torrent.info.file_tree = Some(vec![
+            crate::FileTreeEntry {
+                path: vec!["a".to_string()],
+                length: 111,
+                pieces_root: [0u8; 32],
+            },
+            crate::FileTreeEntry {
+                path: vec!["b".to_string()],
+                length: 222,
+                pieces_root: [0u8; 32],
+            },
+        ]);
+        let request = AnnounceRequest {
+            peer_id: [0u8; 20],
+            port: 6881,
+            uploaded: 0,
+            downloaded: 0,
+            left: None,
+        };
+        assert!(build_query(&torrent, &request).contains("&left=333&"));
+    }
+}
@@ -0,0 +1,484 @@
+//! Verifies on-disk torrent content against the piece hashes in `Info::pieces`.
+//!
+//! The torrent's files are treated as one logical byte stream: in
+//! single-file mode the stream is just `name`; in multi-file mode it is the
+//! concatenation of each `FilesInfo` in list order, joined under the `name`
+//! directory. The stream is read in `piece_length`-sized chunks (the final
+//! piece may be shorter) and each chunk is SHA-1'd against `Info::pieces`.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::{FileTreeEntry, Torrent, sha1, sha256};
+
+/// A logical file in the torrent's byte stream, with the byte range (within
+/// the concatenated stream) that it occupies.
+struct StreamFile {
+    path: PathBuf,
+    start: usize,
+    end: usize,
+}
+
+/// The portion of a single on-disk file that a piece overlaps.
+#[derive(Debug, PartialEq)]
+pub struct FileRange {
+    pub path: PathBuf,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Why a piece could not be read from disk, distinguishing "not there" from
+/// "there but short" so callers can tell which file is corrupt and how.
+#[derive(Debug, PartialEq)]
+pub enum VerifyError {
+    MissingFile(PathBuf),
+    TruncatedFile {
+        path: PathBuf,
+        expected: usize,
+        found: usize,
+    },
+    Io(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PieceStatus {
+    Matched,
+    Mismatched,
+    Error(VerifyError),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PieceReport {
+    pub index: usize,
+    pub status: PieceStatus,
+    /// The file(s) (and byte offsets within each) this piece covers, so a
+    /// failing piece can be mapped back to the file(s) responsible.
+    pub files: Vec<FileRange>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerificationReport {
+    pub pieces: Vec<PieceReport>,
+}
+
+impl VerificationReport {
+    pub fn is_complete(&self) -> bool {
+        self.pieces
+            .iter()
+            .all(|piece| piece.status == PieceStatus::Matched)
+    }
+}
+
+fn stream_files(torrent: &Torrent, root: &Path) -> Vec<StreamFile> {
+    match &torrent.info.files {
+        None => {
+            let length = torrent.info.length.unwrap_or(0);
+            vec![StreamFile {
+                path: root.join(&torrent.info.name),
+                start: 0,
+                end: length,
+            }]
+        }
+        Some(entries) => {
+            let mut offset = 0;
+            entries
+                .iter()
+                .map(|entry| {
+                    let mut path = root.join(&torrent.info.name);
+                    path.extend(&entry.path);
+
+                    let start = offset;
+                    offset += entry.length;
+                    StreamFile {
+                        path,
+                        start,
+                        end: offset,
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+/// Checks `root` against `torrent`'s piece hashes, piece by piece.
+pub fn verify(torrent: &Torrent, root: &Path) -> VerificationReport {
+    let files = stream_files(torrent, root);
+    let piece_length = torrent.info.piece_length.max(1);
+    let stream_length = files.last().map_or(0, |f| f.end);
+    let piece_count = torrent.info.pieces.len() / 20;
+
+    let pieces = (0..piece_count)
+        .map(|index| {
+            let piece_start = index * piece_length;
+            let piece_end = (piece_start + piece_length).min(stream_length);
+
+            let overlapping: Vec<&StreamFile> = files
+                .iter()
+                .filter(|f| f.start < piece_end && f.end > piece_start)
+                .collect();
+
+            let file_ranges = overlapping
+                .iter()
+                .map(|f| FileRange {
+                    path: f.path.clone(),
+                    start: piece_start.max(f.start) - f.start,
+                    end: piece_end.min(f.end) - f.start,
+                })
+                .collect();
+
+            let status = match read_piece(&overlapping, piece_start, piece_end) {
+                Ok(data) => {
+                    let expected = &torrent.info.pieces[index * 20..index * 20 + 20];
+                    if sha1::sha1(&data) == expected {
+                        PieceStatus::Matched
+                    } else {
+                        PieceStatus::Mismatched
+                    }
+                }
+                Err(err) => PieceStatus::Error(err),
+            };
+
+            PieceReport {
+                index,
+                status,
+                files: file_ranges,
+            }
+        })
+        .collect();
+
+    VerificationReport { pieces }
+}
+
+/// Reads the `[piece_start, piece_end)` slice of the logical stream out of
+/// whichever on-disk files `files` says it spans.
+fn read_piece(
+    files: &[&StreamFile],
+    piece_start: usize,
+    piece_end: usize,
+) -> Result<Vec<u8>, VerifyError> {
+    let mut data = Vec::with_capacity(piece_end - piece_start);
+
+    for file in files {
+        let mut handle = File::open(&file.path).map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                VerifyError::MissingFile(file.path.clone())
+            } else {
+                VerifyError::Io(format!("{}: {err}", file.path.display()))
+            }
+        })?;
+
+        let read_start = piece_start.max(file.start) - file.start;
+        let read_end = piece_end.min(file.end) - file.start;
+        let want = read_end - read_start;
+
+        handle
+            .seek(SeekFrom::Start(read_start as u64))
+            .map_err(|err| VerifyError::Io(format!("{}: {err}", file.path.display())))?;
+
+        let mut buf = vec![0u8; want];
+        let mut filled = 0;
+        while filled < want {
+            let n = handle
+                .read(&mut buf[filled..])
+                .map_err(|err| VerifyError::Io(format!("{}: {err}", file.path.display())))?;
+            if n == 0 {
+                return Err(VerifyError::TruncatedFile {
+                    path: file.path.clone(),
+                    expected: read_end,
+                    found: read_start + filled,
+                });
+            }
+            filled += n;
+        }
+
+        data.extend_from_slice(&buf);
+    }
+
+    Ok(data)
+}
+
+/// The verification outcome for a single BEP 52 v2 file.
+#[derive(Debug, PartialEq)]
+pub struct V2FileReport {
+    pub path: PathBuf,
+    pub status: PieceStatus,
+}
+
+/// Checks each file in a v2 (or hybrid) torrent's `file tree` against its
+/// `pieces root`. A no-op on v1-only torrents, which carry no `file tree`.
+pub fn verify_v2(torrent: &Torrent, root: &Path) -> Vec<V2FileReport> {
+    let Some(file_tree) = &torrent.info.file_tree else {
+        return Vec::new();
+    };
+
+    file_tree
+        .iter()
+        .map(|entry| {
+            let mut path = root.join(&torrent.info.name);
+            path.extend(&entry.path);
+
+            let status = match verify_v2_file(&path, entry) {
+                Ok(true) => PieceStatus::Matched,
+                Ok(false) => PieceStatus::Mismatched,
+                Err(err) => PieceStatus::Error(err),
+            };
+
+            V2FileReport { path, status }
+        })
+        .collect()
+}
+
+const V2_BLOCK_SIZE: usize = 16 * 1024;
+
+/// Verifies a single v2 file against its `pieces root`: leaves are SHA-256 of
+/// 16 KiB blocks, internal nodes are SHA-256 of the concatenated children,
+/// and the tree is padded with zero-hash leaves up to the next power of two.
+fn verify_v2_file(path: &Path, entry: &FileTreeEntry) -> Result<bool, VerifyError> {
+    if entry.length == 0 {
+        return Ok(entry.pieces_root == [0u8; 32]);
+    }
+
+    let mut file = File::open(path).map_err(|err| {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            VerifyError::MissingFile(path.to_path_buf())
+        } else {
+            VerifyError::Io(format!("{}: {err}", path.display()))
+        }
+    })?;
+
+    let mut leaves = Vec::with_capacity(entry.length.div_ceil(V2_BLOCK_SIZE));
+    let mut buf = vec![0u8; V2_BLOCK_SIZE];
+    let mut total_read = 0;
+
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|err| VerifyError::Io(format!("{}: {err}", path.display())))?;
+        if n == 0 {
+            break;
+        }
+        leaves.push(sha256::sha256(&buf[..n]));
+        total_read += n;
+    }
+
+    if total_read != entry.length {
+        return Err(VerifyError::TruncatedFile {
+            path: path.to_path_buf(),
+            expected: entry.length,
+            found: total_read,
+        });
+    }
+
+    Ok(merkle_root(&leaves) == entry.pieces_root)
+}
+
+/// Computes a BEP 52 Merkle root from leaf hashes, zero-padding up to the
+/// next power of two so partial final layers hash consistently.
+fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0u8; 32];
+    }
+
+    let mut level = leaves.to_vec();
+    level.resize(level.len().next_power_of_two(), [0u8; 32]);
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut buf = [0u8; 64];
+                buf[..32].copy_from_slice(&pair[0]);
+                buf[32..].copy_from_slice(&pair[1]);
+                sha256::sha256(&buf)
+            })
+            .collect();
+    }
+
+    level[0]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    fn scratch_dir() -> PathBuf {
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("btrust-verify-test-{}-{id}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn torrent_for(name: &str, piece_length: usize, chunks: &[&[u8]]) -> Torrent {
+        let pieces = chunks
+            .iter()
+            .flat_map(|chunk| sha1::sha1(chunk).to_vec())
+            .collect();
+
+        Torrent {
+            announce: "example.com".to_string(),
+            announce_list: None,
+            info_hash: [0u8; 20],
+            info_hash_v2: None,
+            piece_layers: None,
+            info: crate::Info {
+                name: name.to_string(),
+                piece_length,
+                pieces,
+                length: Some(chunks.iter().map(|c| c.len()).sum()),
+                files: None,
+                meta_version: None,
+                file_tree: None,
+            },
+        }
+    }
+
+    #[test]
+    fn single_file_all_pieces_match() {
+        let root = scratch_dir();
+        let torrent = torrent_for("data.bin", 4, &[b"abcd", b"ef"]);
+        fs::write(root.join("data.bin"), b"abcdef").unwrap();
+
+        let report = verify(&torrent, &root);
+        assert!(report.is_complete());
+        assert_eq!(report.pieces.len(), 2);
+    }
+
+    #[test]
+    fn corrupted_piece_is_reported_as_mismatched() {
+        let root = scratch_dir();
+        let torrent = torrent_for("data.bin", 4, &[b"abcd", b"ef"]);
+        fs::write(root.join("data.bin"), b"XXXXef").unwrap();
+
+        let report = verify(&torrent, &root);
+        assert_eq!(report.pieces[0].status, PieceStatus::Mismatched);
+        assert_eq!(report.pieces[1].status, PieceStatus::Matched);
+    }
+
+    #[test]
+    fn missing_file_is_a_distinct_error() {
+        let root = scratch_dir();
+        let torrent = torrent_for("data.bin", 4, &[b"abcd"]);
+
+        let report = verify(&torrent, &root);
+        assert_eq!(
+            report.pieces[0].status,
+            PieceStatus::Error(VerifyError::MissingFile(root.join("data.bin")))
+        );
+    }
+
+    #[test]
+    fn truncated_file_is_a_distinct_error() {
+        let root = scratch_dir();
+        let torrent = torrent_for("data.bin", 4, &[b"abcd"]);
+        fs::write(root.join("data.bin"), b"ab").unwrap();
+
+        let report = verify(&torrent, &root);
+        assert_eq!(
+            report.pieces[0].status,
+            PieceStatus::Error(VerifyError::TruncatedFile {
+                path: root.join("data.bin"),
+                expected: 4,
+                found: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn multi_file_piece_spans_two_files() {
+        let root = scratch_dir();
+        let mut torrent = torrent_for("dir", 4, &[b"abcd"]);
+        torrent.info.length = None;
+        torrent.info.files = Some(vec![
+            crate::FilesInfo {
+                length: 2,
+                path: vec!["a.bin".to_string()],
+            },
+            crate::FilesInfo {
+                length: 2,
+                path: vec!["b.bin".to_string()],
+            },
+        ]);
+
+        fs::create_dir_all(root.join("dir")).unwrap();
+        fs::write(root.join("dir").join("a.bin"), b"ab").unwrap();
+        fs::write(root.join("dir").join("b.bin"), b"cd").unwrap();
+
+        let report = verify(&torrent, &root);
+        assert!(report.is_complete());
+        assert_eq!(report.pieces[0].files.len(), 2);
+    }
+
+    fn pieces_root_for(content: &[u8]) -> [u8; 32] {
+        let leaves: Vec<[u8; 32]> = content.chunks(V2_BLOCK_SIZE).map(sha256::sha256).collect();
+        merkle_root(&leaves)
+    }
+
+    fn v2_torrent_for(name: &str, entries: Vec<FileTreeEntry>) -> Torrent {
+        let mut torrent = torrent_for(name, 16 * 1024, &[]);
+        torrent.info.length = None;
+        torrent.info.meta_version = Some(2);
+        torrent.info.file_tree = Some(entries);
+        torrent
+    }
+
+    #[test]
+    fn v2_file_matches() {
+        let root = scratch_dir();
+        let content = b"hello world, this is v2 content".repeat(100);
+        let entry = FileTreeEntry {
+            path: vec!["file.bin".to_string()],
+            length: content.len(),
+            pieces_root: pieces_root_for(&content),
+        };
+        let torrent = v2_torrent_for("v2dir", vec![entry]);
+
+        fs::create_dir_all(root.join("v2dir")).unwrap();
+        fs::write(root.join("v2dir").join("file.bin"), &content).unwrap();
+
+        let reports = verify_v2(&torrent, &root);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].status, PieceStatus::Matched);
+    }
+
+    #[test]
+    fn v2_file_mismatch_is_reported() {
+        let root = scratch_dir();
+        let content = b"hello world".to_vec();
+        let entry = FileTreeEntry {
+            path: vec!["file.bin".to_string()],
+            length: content.len(),
+            pieces_root: pieces_root_for(&content),
+        };
+        let torrent = v2_torrent_for("v2dir", vec![entry]);
+
+        fs::create_dir_all(root.join("v2dir")).unwrap();
+        fs::write(root.join("v2dir").join("file.bin"), b"goodbye wor").unwrap();
+
+        let reports = verify_v2(&torrent, &root);
+        assert_eq!(reports[0].status, PieceStatus::Mismatched);
+    }
+
+    #[test]
+    fn v2_missing_file_is_a_distinct_error() {
+        let root = scratch_dir();
+        let entry = FileTreeEntry {
+            path: vec!["file.bin".to_string()],
+            length: 11,
+            pieces_root: pieces_root_for(b"hello world"),
+        };
+        let torrent = v2_torrent_for("v2dir", vec![entry]);
+
+        let reports = verify_v2(&torrent, &root);
+        assert_eq!(
+            reports[0].status,
+            PieceStatus::Error(VerifyError::MissingFile(
+                root.join("v2dir").join("file.bin")
+            ))
+        );
+    }
+}